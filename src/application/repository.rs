@@ -0,0 +1,29 @@
+use near_sdk::env;
+
+/// Derives a thread id from its title and creator, namespacing titles per account so two users
+/// can each create a thread titled e.g. "Who wins?" without colliding.
+pub fn convert_title_to_id(title: &str, creator_id: String) -> String {
+  format!("{}-{}", creator_id, slugify(title))
+}
+
+/// Derives a space id from its name alone, since space names are globally unique.
+pub fn convert_title_to_id_no_account(space_name: &str) -> String {
+  slugify(space_name)
+}
+
+fn slugify(value: &str) -> String {
+  value
+    .trim()
+    .to_lowercase()
+    .chars()
+    .map(|c| if c.is_alphanumeric() { c } else { '-' })
+    .collect()
+}
+
+pub fn hash_account_id(account_id: &near_sdk::AccountId) -> Vec<u8> {
+  env::sha256(account_id.as_bytes())
+}
+
+pub fn hash_space_id(space_id: &str) -> Vec<u8> {
+  env::sha256(space_id.as_bytes())
+}
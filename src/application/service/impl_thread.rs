@@ -4,9 +4,12 @@ use crate::{
   application::repository::{convert_title_to_id, convert_title_to_id_no_account, hash_account_id, hash_space_id},
   models::{
     contract::{StorageKey, ThreadScoreContract, ThreadScoreContractExt},
-    space::SpaceFeatures,
-    thread::{ThreadFeatures, ThreadId, ThreadMetadata, ThreadState},
-    user::{UserId, UserRoles},
+    space::{SpaceFeatures, SpaceId},
+    thread::{
+      ThreadFeatures, ThreadId, ThreadMetadata, ThreadState, DEFAULT_SUPERMAJORITY_THRESHOLD, INITIAL_LOCKOUT_MS,
+      MAX_LOCKOUT_DOUBLINGS,
+    },
+    user::{UserId, UserRoles, EPOCH_DURATION_MS},
   },
 };
 use near_sdk::{borsh::BorshSerialize, json_types::U64};
@@ -24,17 +27,28 @@ impl ThreadFeatures for ThreadScoreContract {
     start_time: U64,
     end_time: U64,
     options: Vec<String>,
+    supermajority_threshold: Option<u8>,
   ) -> ThreadMetadata {
     let creator_id = env::signer_account_id();
 
+    let supermajority_threshold = supermajority_threshold.unwrap_or(DEFAULT_SUPERMAJORITY_THRESHOLD);
+    assert!(
+      (51..=90).contains(&supermajority_threshold),
+      "Supermajority threshold must be between 51 and 90!"
+    );
+
     // check option have at least 2
     assert!(options.len() > 1, "Vote option must be greater than 2!");
     assert!(options.len() < 4, "Vote option must be less than 4!");
 
     let mut choices_map = HashMap::<u8, String>::new();
 
+    let mut choices_rating = HashMap::<u8, u32>::new();
+
     options.iter().enumerate().for_each(|(idx, option)| {
       choices_map.insert(idx as u8, option.to_owned());
+      // seed every choice with zero points so `vote_thread`'s `get_mut` has an entry to update
+      choices_rating.insert(idx as u8, 0_u32);
     });
 
     let thread_id = convert_title_to_id(&title, creator_id.to_string());
@@ -48,7 +62,7 @@ impl ThreadFeatures for ThreadScoreContract {
       None => assert!(false, "Your account is not created!"),
     }
 
-    assert!(self.thread_metadata_by_id.get(&thread_id).is_none(), "This thread already created!");
+    assert!(self.get_thread_metadata(&thread_id).is_none(), "This thread already created!");
 
     let thread_meta = ThreadMetadata {
       thread_id: thread_id.clone(),
@@ -64,8 +78,12 @@ impl ThreadFeatures for ThreadScoreContract {
       choices_count: options.len() as u8,
       choices_map,
       user_votes_map: HashMap::new(),
-      choices_rating: HashMap::new(),
+      choices_rating,
       last_id: 0_u32,
+      settled: false,
+      winning_choice: None,
+      supermajority_threshold,
+      resolved_early: false,
     };
 
     let init_new_user_threads_list: UnorderedSet<String> = UnorderedSet::new(
@@ -82,10 +100,10 @@ impl ThreadFeatures for ThreadScoreContract {
 
     self.threads_per_user.insert(&creator_id, &new_user_threads_list);
 
-    self.thread_metadata_by_id.insert(&thread_id, &thread_meta);
+    self.set_thread_metadata(&thread_id, &thread_meta);
 
     let space_id = convert_title_to_id_no_account(&space_name);
-    let is_space_id_exists = self.space_metadata_by_id.contains_key(&space_id);
+    let is_space_id_exists = self.get_space_metadata(&space_id).is_some();
 
     if !is_space_id_exists {
       self.create_space(space_name);
@@ -119,7 +137,7 @@ impl ThreadFeatures for ThreadScoreContract {
   }
 
   fn get_thread_metadata_by_thread_id(&self, thread_id: ThreadId) -> Option<ThreadMetadata> {
-    let found_thread = self.thread_metadata_by_id.get(&thread_id);
+    let found_thread = self.get_thread_metadata(&thread_id);
     found_thread
   }
 
@@ -135,7 +153,7 @@ impl ThreadFeatures for ThreadScoreContract {
     let thread_array = self.threads_per_user.get(&user_id).unwrap();
 
     for thread_id in thread_array.iter().skip(start.unwrap_or(0_u32) as usize).take(limit.unwrap_or(5) as usize) {
-      let thread_found = self.thread_metadata_by_id.get(&thread_id);
+      let thread_found = self.get_thread_metadata(&thread_id);
       result.push(thread_found.unwrap());
     }
 
@@ -144,10 +162,14 @@ impl ThreadFeatures for ThreadScoreContract {
 
   // Check thread status
   fn get_thread_status(&self, thread_id: &ThreadId) -> ThreadState {
-    let thread_found = self.thread_metadata_by_id.get(&thread_id);
+    let thread_found = self.get_thread_metadata(&thread_id);
 
     assert!(thread_found.is_some(), "Thread not existed!");
 
+    if thread_found.clone().unwrap().resolved_early {
+      return ThreadState::Resolved;
+    }
+
     let current_time = env::block_timestamp_ms();
     let start_time = thread_found.clone().unwrap().start_time;
     let end_time = thread_found.unwrap().end_time;
@@ -163,11 +185,40 @@ impl ThreadFeatures for ThreadScoreContract {
     return ThreadState::Upcoming;
   }
 
-  fn vote_thread(&mut self, thread_id: ThreadId, choice_number: u8, point: u32) -> Option<String> {
-    let voter = env::signer_account_id();
+  fn vote_thread(
+    &mut self,
+    thread_id: ThreadId,
+    choice_number: u8,
+    point: u32,
+    on_behalf_of: Option<UserId>,
+  ) -> Option<String> {
+    let signer = env::signer_account_id();
 
     assert!(point > 10, "Your point must be greater than 10!");
 
+    // check thread id valid
+    let thread_found = self.get_thread_metadata(&thread_id);
+    assert!(thread_found.is_some(), "Thread is not existed!");
+
+    let space_id = convert_title_to_id_no_account(&thread_found.clone().unwrap().space_name);
+
+    // resolve the effective staker: the signer themselves, or a delegator who has authorized
+    // the signer to vote on their behalf within this thread's space
+    let voter = match &on_behalf_of {
+      Some(delegator) => {
+        let delegation = self.authorized_voter_by_space.get(&(delegator.clone(), space_id));
+        match delegation {
+          Some((delegate, expires_at)) if delegate == signer => {
+            let is_expired = expires_at.map_or(false, |expiry| env::block_timestamp_ms() > expiry);
+            assert!(!is_expired, "This delegation has expired!");
+          },
+          _ => assert!(false, "You are not an authorized voter for this account in this space!"),
+        }
+        delegator.clone()
+      },
+      None => signer,
+    };
+
     // check point of user > initial point
     let found_voter = self.user_metadata_by_id.get(&voter);
     assert!(found_voter.is_some(), "This user is not existed!");
@@ -176,33 +227,49 @@ impl ThreadFeatures for ThreadScoreContract {
       assert!(json_user.total_point > point, "You don't have enough point!");
     }
 
-    // check thread id valid
-    let thread_found = self.thread_metadata_by_id.get(&thread_id);
-    assert!(thread_found.is_some(), "Thread is not existed!");
-
     // check time is valid
 
     let cur_thread_state = self.get_thread_status(&thread_id);
     assert!(cur_thread_state != ThreadState::Upcoming, "This thread is not live yet!");
     assert!(cur_thread_state != ThreadState::Closed, "This thread is ended!");
+    assert!(cur_thread_state != ThreadState::Resolved, "This thread is ended!");
 
     // check choice is valid
     if let Some(mut thread_metadata) = thread_found {
       assert!(thread_metadata.choices_map.get(&choice_number).is_some(), "Your choice is not valid!");
 
-      // update user_votes_map
-      let new_user_votes_map = thread_metadata.user_votes_map.get_key_value(&voter);
+      let now = env::block_timestamp_ms();
+
+      // re-affirming the same choice doubles the lockout (tower-style); switching choices is not allowed
+      let (total_stake, confirmation_count) = match thread_metadata.user_votes_map.get(&voter) {
+        Some((existing_choice, existing_stake, _, confirmation_count)) => {
+          assert!(*existing_choice == choice_number, "You already voted for a different choice!");
+          (existing_stake + point, confirmation_count.saturating_add(1).min(MAX_LOCKOUT_DOUBLINGS as u8))
+        },
+        None => (point, 0_u8),
+      };
 
-      assert!(new_user_votes_map.is_none(), "This user already voted!");
+      let lockout_doublings = (confirmation_count as u32).min(MAX_LOCKOUT_DOUBLINGS);
+      let lockout_expiry = now + INITIAL_LOCKOUT_MS * 2_u64.pow(lockout_doublings);
 
-      thread_metadata.user_votes_map.insert(voter.clone(), (choice_number, point));
+      thread_metadata.user_votes_map.insert(voter.clone(), (choice_number, total_stake, lockout_expiry, confirmation_count));
 
       // update choices_rating
       if let Some(cur_point) = thread_metadata.choices_rating.get_mut(&choice_number) {
         *cur_point += point;
       }
 
-      self.thread_metadata_by_id.insert(&thread_id, &thread_metadata);
+      // auto-resolve once a single choice crosses the thread's supermajority threshold
+      let total_staked_points: u32 = thread_metadata.choices_rating.values().sum();
+      let winning_choice_points = *thread_metadata.choices_rating.get(&choice_number).unwrap_or(&0);
+
+      if total_staked_points > 0
+        && (winning_choice_points as u64) * 100 >= (total_staked_points as u64) * (thread_metadata.supermajority_threshold as u64)
+      {
+        thread_metadata.resolved_early = true;
+      }
+
+      self.set_thread_metadata(&thread_id, &thread_metadata);
     }
 
     // update new point for user
@@ -215,17 +282,385 @@ impl ThreadFeatures for ThreadScoreContract {
     Some("OK".to_string())
   }
 
+  fn get_trust_score(&self, user_id: UserId) -> u64 {
+    match self.user_metadata_by_id.get(&user_id) {
+      Some(user_metadata) => user_metadata.trust_score(),
+      None => 0,
+    }
+  }
+
+  fn set_authorized_voter(&mut self, space_id: SpaceId, delegate: UserId, expires_at: Option<U64>) -> Option<String> {
+    let delegator = env::signer_account_id();
+
+    assert!(self.get_space_metadata(&space_id).is_some(), "This space is not existed!");
+    assert!(delegate != delegator, "You cannot delegate to yourself!");
+
+    self
+      .authorized_voter_by_space
+      .insert(&(delegator, space_id), &(delegate, expires_at.map(|timestamp| timestamp.into())));
+
+    Some("OK".to_string())
+  }
+
   fn end_thread(&mut self, thread_id: ThreadId) -> Option<String> {
-    // check thread status
+    let caller = env::signer_account_id();
+
+    let thread_found = self.get_thread_metadata(&thread_id);
+    assert!(thread_found.is_some(), "Thread is not existed!");
+
+    let mut thread_metadata = thread_found.unwrap();
+    assert!(!thread_metadata.settled, "This thread is already settled!");
+    let status = self.get_thread_status(&thread_id);
+    assert!(status == ThreadState::Closed || status == ThreadState::Resolved, "This thread is not closed yet!");
+
+    if caller != thread_metadata.creator_id {
+      let caller_metadata = self.user_metadata_by_id.get(&caller);
+      assert!(caller_metadata.is_some(), "This user is not existed!");
+      assert!(
+        caller_metadata.unwrap().metadata.role == UserRoles::Admin,
+        "Only the creator or an admin can end this thread!"
+      );
+    }
+
+    // tally staked points per choice, and find the winning choice
+    let total_staked_points: u32 = thread_metadata.choices_rating.values().sum();
+
+    // `HashMap` iteration order is not guaranteed, so break ties deterministically in favor of
+    // the lowest `choice_number` instead of whichever entry the hash order visits last.
+    let winning_choice = thread_metadata
+      .choices_rating
+      .iter()
+      .fold(None, |best: Option<(u8, u32)>, (&choice, &points)| match best {
+        Some((best_choice, best_points)) if points < best_points || (points == best_points && choice >= best_choice) => {
+          Some((best_choice, best_points))
+        },
+        _ => Some((choice, points)),
+      })
+      .map(|(choice, _)| choice);
+
+    let winning_choice = match winning_choice {
+      Some(choice) if total_staked_points > 0 => choice,
+      _ => {
+        thread_metadata.settled = true;
+        self.set_thread_metadata(&thread_id, &thread_metadata);
+        return Some("No votes were cast, nothing to settle.".to_string());
+      },
+    };
+
+    let winning_stake = *thread_metadata.choices_rating.get(&winning_choice).unwrap_or(&0);
+    let losing_stake = total_staked_points.saturating_sub(winning_stake);
+
+    let current_epoch = env::block_timestamp_ms() / EPOCH_DURATION_MS;
+
+    // credit every winning voter their proportional share of the losing pot now; their own
+    // stake stays locked and is reclaimed separately through `withdraw_locked_points`
+    for (voter, (choice, stake, _, _)) in thread_metadata.user_votes_map.iter() {
+      if *choice != winning_choice || winning_stake == 0 {
+        continue;
+      }
+
+      if let Some(mut voter_metadata) = self.user_metadata_by_id.get(voter) {
+        let reward = ((*stake as u64) * (losing_stake as u64) / (winning_stake as u64)) as u32;
+        voter_metadata.total_point += reward;
+        voter_metadata.record_epoch_credits(current_epoch, reward as u64);
+        self.user_metadata_by_id.insert(voter, &voter_metadata);
+      }
+    }
+
+    thread_metadata.settled = true;
+    thread_metadata.winning_choice = Some(winning_choice);
+    self.set_thread_metadata(&thread_id, &thread_metadata);
+
+    Some("OK".to_string())
+  }
+
+  fn withdraw_locked_points(&mut self, thread_id: ThreadId) -> Option<String> {
+    let voter = env::signer_account_id();
+
+    let thread_found = self.get_thread_metadata(&thread_id);
+    assert!(thread_found.is_some(), "Thread is not existed!");
+
+    let mut thread_metadata = thread_found.unwrap();
+    let status = self.get_thread_status(&thread_id);
+    assert!(status == ThreadState::Closed || status == ThreadState::Resolved, "This thread is not closed yet!");
+    assert!(thread_metadata.settled, "This thread has not been settled yet!");
+
+    let vote_found = thread_metadata.user_votes_map.get(&voter);
+    assert!(vote_found.is_some(), "You did not vote on this thread!");
+    let (choice, stake, lockout_expiry, _) = *vote_found.unwrap();
+
+    assert!(Some(choice) == thread_metadata.winning_choice, "Your stake was forfeited to the winning side!");
+    assert!(env::block_timestamp_ms() >= lockout_expiry, "Your stake is still locked!");
+
+    thread_metadata.user_votes_map.remove(&voter);
+    self.set_thread_metadata(&thread_id, &thread_metadata);
+
+    let voter_metadata_found = self.user_metadata_by_id.get(&voter);
+    assert!(voter_metadata_found.is_some(), "This user is not existed!");
+    let mut voter_metadata = voter_metadata_found.unwrap();
+    voter_metadata.total_point += stake;
+    self.user_metadata_by_id.insert(&voter, &voter_metadata);
+
+    Some("OK".to_string())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::models::{space::SpaceMetadata, user::{UserMetadata, UserProfileMetadata, UserRoles}};
+  use near_sdk::{test_utils::{accounts, VMContextBuilder}, testing_env, AccountId};
+
+  fn set_context(signer: AccountId, block_timestamp_ms: u64) {
+    let context = VMContextBuilder::new()
+      .signer_account_id(signer.clone())
+      .predecessor_account_id(signer)
+      .block_timestamp(block_timestamp_ms * 1_000_000)
+      .build();
+    testing_env!(context);
+  }
+
+  fn new_user(user_id: AccountId, total_point: u32) -> UserMetadata {
+    UserMetadata {
+      user_id,
+      metadata: UserProfileMetadata { role: UserRoles::Verified, username: None },
+      total_point,
+      threads_owned: 0,
+      threads_list: Vec::new(),
+      created_at: 0,
+      epoch_credits_history: Default::default(),
+    }
+  }
+
+  fn new_thread(thread_id: ThreadId, creator_id: AccountId, start_time: u64, end_time: u64) -> ThreadMetadata {
+    let mut choices_map = HashMap::new();
+    choices_map.insert(0_u8, "Yes".to_string());
+    choices_map.insert(1_u8, "No".to_string());
+
+    let mut choices_rating = HashMap::new();
+    choices_rating.insert(0_u8, 0_u32);
+    choices_rating.insert(1_u8, 0_u32);
+
+    ThreadMetadata {
+      thread_id,
+      title: "Test thread".to_string(),
+      media_link: None,
+      creator_id,
+      content: None,
+      init_point: 10,
+      space_name: "general".to_string(),
+      start_time,
+      end_time,
+      created_at: 0,
+      choices_count: 2,
+      choices_map,
+      user_votes_map: HashMap::new(),
+      choices_rating,
+      last_id: 0,
+      settled: false,
+      winning_choice: None,
+      supermajority_threshold: DEFAULT_SUPERMAJORITY_THRESHOLD,
+      resolved_early: false,
+    }
+  }
+
+  #[test]
+  fn end_thread_breaks_choice_rating_ties_toward_the_lowest_choice_number() {
+    let creator = accounts(0);
+    set_context(creator.clone(), 0);
+    let mut contract = ThreadScoreContract::default();
+
+    let thread_id = "tie-thread".to_string();
+    let mut thread = new_thread(thread_id.clone(), creator.clone(), 0, 1_000);
+    thread.choices_rating.insert(0, 50);
+    thread.choices_rating.insert(1, 50);
+    contract.set_thread_metadata(&thread_id, &thread);
+    contract.user_metadata_by_id.insert(&creator, &new_user(creator.clone(), 100));
+
+    set_context(creator, 2_000);
+    contract.end_thread(thread_id.clone());
+
+    let settled = contract.get_thread_metadata(&thread_id).unwrap();
+    assert_eq!(settled.winning_choice, Some(0));
+  }
 
-    // check is admin
+  #[test]
+  fn end_thread_credits_winners_their_proportional_share_of_the_losing_pot() {
+    let creator = accounts(0);
+    let winner = accounts(1);
+    let loser = accounts(2);
+    set_context(creator.clone(), 0);
+    let mut contract = ThreadScoreContract::default();
+
+    let thread_id = "payout-thread".to_string();
+    let mut thread = new_thread(thread_id.clone(), creator.clone(), 0, 1_000);
+    thread.choices_rating.insert(0, 30);
+    thread.choices_rating.insert(1, 70);
+    thread.user_votes_map.insert(winner.clone(), (0, 30, 0, 0));
+    thread.user_votes_map.insert(loser.clone(), (1, 70, 0, 0));
+    contract.set_thread_metadata(&thread_id, &thread);
+
+    contract.user_metadata_by_id.insert(&creator, &new_user(creator.clone(), 100));
+    contract.user_metadata_by_id.insert(&winner, &new_user(winner.clone(), 0));
+    contract.user_metadata_by_id.insert(&loser, &new_user(loser.clone(), 0));
+
+    set_context(creator, 2_000);
+    contract.end_thread(thread_id.clone());
+
+    // reward = stake * losing_stake / winning_stake = 30 * 70 / 30
+    let winner_metadata = contract.user_metadata_by_id.get(&winner).unwrap();
+    assert_eq!(winner_metadata.total_point, 70);
+    assert_eq!(winner_metadata.epoch_credits_history.back().unwrap().1, 70);
+
+    let settled = contract.get_thread_metadata(&thread_id).unwrap();
+    assert!(settled.settled);
+    assert_eq!(settled.winning_choice, Some(0));
+  }
+
+  #[test]
+  fn vote_thread_doubles_lockout_on_reaffirm_and_caps_it() {
+    let creator = accounts(0);
+    let voter = accounts(1);
+    set_context(creator.clone(), 0);
+    let mut contract = ThreadScoreContract::default();
+
+    let thread_id = "lockout-thread".to_string();
+    let mut thread = new_thread(thread_id.clone(), creator, 0, u64::MAX - 1);
+    // opposing stake on choice 1 keeps choice 0 below the supermajority threshold, so the
+    // thread stays open for the re-affirming votes below
+    thread.choices_rating.insert(1, 1_000);
+    contract.set_thread_metadata(&thread_id, &thread);
+    contract.user_metadata_by_id.insert(&voter, &new_user(voter.clone(), 1_000));
+
+    set_context(voter.clone(), 500);
+    contract.vote_thread(thread_id.clone(), 0, 20, None);
+    let (_, _, first_expiry, first_count) =
+      contract.get_thread_metadata(&thread_id).unwrap().user_votes_map.get(&voter).copied().unwrap();
+    assert_eq!(first_count, 0);
+    assert_eq!(first_expiry, 500 + INITIAL_LOCKOUT_MS);
+
+    contract.vote_thread(thread_id.clone(), 0, 20, None);
+    let (_, _, second_expiry, second_count) =
+      contract.get_thread_metadata(&thread_id).unwrap().user_votes_map.get(&voter).copied().unwrap();
+    assert_eq!(second_count, 1);
+    assert_eq!(second_expiry, 500 + INITIAL_LOCKOUT_MS * 2);
+
+    // jump straight to the cap instead of reaffirming dozens of times for real
+    let mut near_cap = contract.get_thread_metadata(&thread_id).unwrap();
+    near_cap.user_votes_map.insert(voter.clone(), (0, 40, 500, MAX_LOCKOUT_DOUBLINGS as u8));
+    contract.set_thread_metadata(&thread_id, &near_cap);
+
+    contract.vote_thread(thread_id.clone(), 0, 20, None);
+    let (_, _, capped_expiry, capped_count) =
+      contract.get_thread_metadata(&thread_id).unwrap().user_votes_map.get(&voter).copied().unwrap();
+    assert_eq!(capped_count, MAX_LOCKOUT_DOUBLINGS as u8);
+    assert_eq!(capped_expiry, 500 + INITIAL_LOCKOUT_MS * 2_u64.pow(MAX_LOCKOUT_DOUBLINGS));
+  }
+
+  #[test]
+  fn set_authorized_voter_lets_the_delegate_vote_using_the_delegators_points() {
+    let creator = accounts(0);
+    let delegator = accounts(1);
+    let delegate = accounts(2);
+    set_context(creator.clone(), 0);
+    let mut contract = ThreadScoreContract::default();
+
+    let space_id = "general".to_string();
+    contract.set_space_metadata(
+      &space_id,
+      &SpaceMetadata {
+        space_id: space_id.clone(),
+        space_name: "general".to_string(),
+        creator_id: creator.clone(),
+        created_at: 0,
+        followed_users: Vec::new(),
+        total_point: 0,
+      },
+    );
 
-    // calculate which win
+    let thread_id = "delegate-thread".to_string();
+    let thread = new_thread(thread_id.clone(), creator, 0, u64::MAX - 1);
+    contract.set_thread_metadata(&thread_id, &thread);
 
-    // calc total point
-    
+    contract.user_metadata_by_id.insert(&delegator, &new_user(delegator.clone(), 100));
+    contract.user_metadata_by_id.insert(&delegate, &new_user(delegate.clone(), 100));
 
+    set_context(delegator.clone(), 10);
+    contract.set_authorized_voter(space_id, delegate.clone(), None);
+
+    set_context(delegate.clone(), 20);
+    contract.vote_thread(thread_id.clone(), 0, 30, Some(delegator.clone()));
+
+    let delegator_metadata = contract.user_metadata_by_id.get(&delegator).unwrap();
+    let delegate_metadata = contract.user_metadata_by_id.get(&delegate).unwrap();
+    assert_eq!(delegator_metadata.total_point, 70);
+    assert_eq!(delegate_metadata.total_point, 100);
+
+    let settled_thread = contract.get_thread_metadata(&thread_id).unwrap();
+    assert!(settled_thread.user_votes_map.contains_key(&delegator));
+    assert!(!settled_thread.user_votes_map.contains_key(&delegate));
+  }
+
+  #[test]
+  fn vote_thread_auto_resolves_once_a_choice_crosses_the_supermajority_threshold() {
+    let creator = accounts(0);
+    let voter = accounts(1);
+    set_context(creator.clone(), 0);
+    let mut contract = ThreadScoreContract::default();
+
+    let thread_id = "supermajority-thread".to_string();
+    let thread = new_thread(thread_id.clone(), creator, 0, u64::MAX - 1);
+    contract.set_thread_metadata(&thread_id, &thread);
+    contract.user_metadata_by_id.insert(&voter, &new_user(voter.clone(), 1_000));
+
+    set_context(voter, 10);
+    contract.vote_thread(thread_id.clone(), 0, 80, None);
+
+    assert_eq!(contract.get_thread_status(&thread_id), ThreadState::Resolved);
+    assert!(contract.get_thread_metadata(&thread_id).unwrap().resolved_early);
+  }
+
+  #[test]
+  #[should_panic(expected = "Your stake is still locked!")]
+  fn withdraw_locked_points_panics_before_the_lockout_expires() {
+    let creator = accounts(0);
+    let voter = accounts(1);
+    set_context(creator.clone(), 0);
+    let mut contract = ThreadScoreContract::default();
+
+    let thread_id = "withdraw-thread".to_string();
+    let mut thread = new_thread(thread_id.clone(), creator, 0, 1_000);
+    thread.settled = true;
+    thread.winning_choice = Some(0);
+    thread.user_votes_map.insert(voter.clone(), (0, 50, 5_000, 0));
+    contract.set_thread_metadata(&thread_id, &thread);
+    contract.user_metadata_by_id.insert(&voter, &new_user(voter.clone(), 0));
+
+    set_context(voter, 2_000);
+    contract.withdraw_locked_points(thread_id);
+  }
 
-    None
+  #[test]
+  fn withdraw_locked_points_returns_the_stake_once_the_lockout_expires() {
+    let creator = accounts(0);
+    let voter = accounts(1);
+    set_context(creator.clone(), 0);
+    let mut contract = ThreadScoreContract::default();
+
+    let thread_id = "withdraw-thread-2".to_string();
+    let mut thread = new_thread(thread_id.clone(), creator, 0, 1_000);
+    thread.settled = true;
+    thread.winning_choice = Some(0);
+    thread.user_votes_map.insert(voter.clone(), (0, 50, 5_000, 0));
+    contract.set_thread_metadata(&thread_id, &thread);
+    contract.user_metadata_by_id.insert(&voter, &new_user(voter.clone(), 0));
+
+    set_context(voter.clone(), 5_000);
+    contract.withdraw_locked_points(thread_id.clone());
+
+    let voter_metadata = contract.user_metadata_by_id.get(&voter).unwrap();
+    assert_eq!(voter_metadata.total_point, 50);
+    let settled = contract.get_thread_metadata(&thread_id).unwrap();
+    assert!(!settled.user_votes_map.contains_key(&voter));
   }
 }
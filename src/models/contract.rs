@@ -0,0 +1,154 @@
+use near_sdk::{
+  borsh::{self, BorshDeserialize, BorshSerialize},
+  collections::{LookupMap, UnorderedMap, UnorderedSet},
+  near_bindgen,
+};
+
+use super::{
+  space::{SpaceId, SpaceMetadata, SpaceMetadataV1},
+  thread::{ThreadId, ThreadMetadata, ThreadMetadataV1},
+  user::{UserId, UserMetadata},
+};
+
+#[derive(BorshSerialize)]
+pub enum StorageKey {
+  /// Original pre-versioning prefix: real deployments already have raw `ThreadMetadataV1`
+  /// entries sitting under this key, so it must stay the legacy map's prefix, not the current
+  /// one's — see `ThreadScoreContract::legacy_thread_metadata_by_id`.
+  ThreadMetadataById,
+  ThreadMetadataV2ById,
+  UserMetadataById,
+  /// Original pre-versioning prefix: real deployments already have raw `SpaceMetadataV1`
+  /// entries sitting under this key, so it must stay the legacy map's prefix, not the current
+  /// one's — see `ThreadScoreContract::legacy_space_metadata_by_id`.
+  SpaceMetadataById,
+  SpaceMetadataV2ById,
+  ThreadsPerUser,
+  ThreadsPerUserInner { account_id_hash: Vec<u8> },
+  ThreadsPerSpace,
+  ThreadsPerSpaceInner { space_id_hash: Vec<u8> },
+  AuthorizedVoterBySpace,
+}
+
+#[near_bindgen]
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct ThreadScoreContract {
+  /// Current-schema thread metadata, under its own storage prefix. Always go through
+  /// `get_thread_metadata`/`set_thread_metadata` instead of touching this field directly.
+  pub thread_metadata_by_id: LookupMap<ThreadId, ThreadMetadata>,
+  /// Raw `ThreadMetadataV1` entries, under the original pre-versioning storage prefix. Never
+  /// written to anymore — `set_thread_metadata` only ever inserts into `thread_metadata_by_id`
+  /// — so a `thread_id` found here is unambiguously pre-versioning data, no tag-sniffing
+  /// required to tell the two apart.
+  pub legacy_thread_metadata_by_id: LookupMap<ThreadId, ThreadMetadataV1>,
+  pub user_metadata_by_id: LookupMap<UserId, UserMetadata>,
+  /// Current-schema space metadata, under its own storage prefix. Always go through
+  /// `get_space_metadata`/`set_space_metadata` instead of touching this field directly.
+  pub space_metadata_by_id: UnorderedMap<SpaceId, SpaceMetadata>,
+  /// Raw `SpaceMetadataV1` entries, under the original pre-versioning storage prefix. Never
+  /// written to anymore — `set_space_metadata` only ever inserts into `space_metadata_by_id` —
+  /// so a `space_id` found here is unambiguously pre-versioning data, no tag-sniffing required
+  /// to tell the two apart.
+  pub legacy_space_metadata_by_id: LookupMap<SpaceId, SpaceMetadataV1>,
+  pub threads_per_user: LookupMap<UserId, UnorderedSet<ThreadId>>,
+  pub threads_per_space: LookupMap<SpaceId, UnorderedSet<ThreadId>>,
+
+  /// `(delegator, space_id)` to the delegate currently authorized to vote on the delegator's
+  /// behalf within that space, and when the delegation expires. A new delegation for the same
+  /// `(delegator, space_id)` overrides the previous one.
+  pub authorized_voter_by_space: LookupMap<(UserId, SpaceId), (UserId, Option<u64>)>,
+}
+
+impl ThreadScoreContract {
+  /// Reads a thread's metadata, lazily upgrading it to the current schema if it was only ever
+  /// written as a pre-versioning `ThreadMetadataV1`. Does not rewrite storage — pair with
+  /// `set_thread_metadata` once you have a mutation to persist.
+  pub fn get_thread_metadata(&self, thread_id: &ThreadId) -> Option<ThreadMetadata> {
+    if let Some(current) = self.thread_metadata_by_id.get(thread_id) {
+      return Some(current);
+    }
+
+    self.legacy_thread_metadata_by_id.get(thread_id).map(ThreadMetadataV1::into_current)
+  }
+
+  /// Persists `metadata` under the current-schema map, so the next read no longer needs to
+  /// migrate it.
+  pub fn set_thread_metadata(&mut self, thread_id: &ThreadId, metadata: &ThreadMetadata) {
+    self.thread_metadata_by_id.insert(thread_id, metadata);
+  }
+
+  /// Reads a space's metadata, lazily upgrading it to the current schema if it was only ever
+  /// written as a pre-versioning `SpaceMetadataV1`. Does not rewrite storage — pair with
+  /// `set_space_metadata` once you have a mutation to persist.
+  pub fn get_space_metadata(&self, space_id: &SpaceId) -> Option<SpaceMetadata> {
+    if let Some(current) = self.space_metadata_by_id.get(space_id) {
+      return Some(current);
+    }
+
+    self.legacy_space_metadata_by_id.get(space_id).map(SpaceMetadataV1::into_current)
+  }
+
+  /// Persists `metadata` under the current-schema map, so the next read no longer needs to
+  /// migrate it.
+  pub fn set_space_metadata(&mut self, space_id: &SpaceId, metadata: &SpaceMetadata) {
+    self.space_metadata_by_id.insert(space_id, metadata);
+  }
+}
+
+impl Default for ThreadScoreContract {
+  fn default() -> Self {
+    Self {
+      thread_metadata_by_id: LookupMap::new(StorageKey::ThreadMetadataV2ById.try_to_vec().unwrap()),
+      legacy_thread_metadata_by_id: LookupMap::new(StorageKey::ThreadMetadataById.try_to_vec().unwrap()),
+      user_metadata_by_id: LookupMap::new(StorageKey::UserMetadataById.try_to_vec().unwrap()),
+      space_metadata_by_id: UnorderedMap::new(StorageKey::SpaceMetadataV2ById.try_to_vec().unwrap()),
+      legacy_space_metadata_by_id: LookupMap::new(StorageKey::SpaceMetadataById.try_to_vec().unwrap()),
+      threads_per_user: LookupMap::new(StorageKey::ThreadsPerUser.try_to_vec().unwrap()),
+      threads_per_space: LookupMap::new(StorageKey::ThreadsPerSpace.try_to_vec().unwrap()),
+      authorized_voter_by_space: LookupMap::new(StorageKey::AuthorizedVoterBySpace.try_to_vec().unwrap()),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::models::thread::ThreadMetadataV1;
+  use std::collections::HashMap;
+
+  #[test]
+  fn get_thread_metadata_falls_back_to_the_legacy_map_for_pre_versioning_entries() {
+    let mut contract = ThreadScoreContract::default();
+
+    let thread_id = "legacy-thread".to_string();
+    let legacy = ThreadMetadataV1 {
+      thread_id: thread_id.clone(),
+      title: "Legacy thread".to_string(),
+      media_link: None,
+      creator_id: "bob.near".parse().unwrap(),
+      content: None,
+      init_point: 10,
+      space_name: "general".to_string(),
+      start_time: 0,
+      end_time: 1_000,
+      created_at: 0,
+      choices_count: 2,
+      choices_map: HashMap::new(),
+      user_votes_map: HashMap::new(),
+      choices_rating: HashMap::new(),
+      last_id: 0,
+    };
+    contract.legacy_thread_metadata_by_id.insert(&thread_id, &legacy);
+
+    assert!(contract.thread_metadata_by_id.get(&thread_id).is_none());
+    let upgraded = contract.get_thread_metadata(&thread_id).unwrap();
+    assert_eq!(upgraded.thread_id, thread_id);
+    assert!(!upgraded.settled);
+
+    // once rewritten through `set_thread_metadata`, the current map takes priority, and the
+    // legacy entry is left exactly as it was (it's never written to again)
+    contract.set_thread_metadata(&thread_id, &upgraded);
+    assert!(contract.thread_metadata_by_id.get(&thread_id).is_some());
+    assert!(contract.legacy_thread_metadata_by_id.get(&thread_id).is_some());
+  }
+}
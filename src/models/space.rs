@@ -33,6 +33,39 @@ pub struct SpaceMetadata {
   pub total_point : u64
 }
 
+/// The original shape of `SpaceMetadata`, from before `followed_users` and `total_point` were
+/// added. Kept only so `SpaceMetadataV1::into_current` can migrate old entries. Lives only in
+/// `ThreadScoreContract::legacy_space_metadata_by_id` — see that field's doc comment.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, JsonSchema)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SpaceMetadataV1 {
+  pub space_id: SpaceId,
+  pub space_name: String,
+  pub creator_id: UserId,
+  pub created_at: u64,
+}
+
+impl SpaceMetadataV1 {
+  /// Upgrades a pre-versioning entry to the current `SpaceMetadata` shape, filling sane
+  /// defaults for fields that didn't exist yet when it was written.
+  ///
+  /// There is no tag to branch on here: `SpaceMetadataV1` entries only ever live in
+  /// `ThreadScoreContract::legacy_space_metadata_by_id`, a storage prefix that's never written
+  /// to after this migration shipped, while current entries live under the separate
+  /// `space_metadata_by_id` prefix. Which shape a given `space_id` is in is therefore determined
+  /// by which map the key is found in, not by guessing from its serialized bytes.
+  pub fn into_current(self) -> SpaceMetadata {
+    SpaceMetadata {
+      space_id: self.space_id,
+      space_name: self.space_name,
+      creator_id: self.creator_id,
+      created_at: self.created_at,
+      followed_users: Vec::new(),
+      total_point: 0,
+    }
+  }
+}
+
 pub trait SpaceFeatures {
   fn create_space(&mut self, space_name: String) -> SpaceMetadata;
 
@@ -48,3 +81,23 @@ pub trait SpaceFeatures {
 
   fn get_followed_user_of_space_by_space_id(&self, space_id: SpaceId) -> Vec<UserId>;
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn space_metadata_v1_upgrades_with_sane_defaults() {
+    let legacy = SpaceMetadataV1 {
+      space_id: "old-space".to_string(),
+      space_name: "Old space".to_string(),
+      creator_id: "bob.near".parse().unwrap(),
+      created_at: 0,
+    };
+
+    let current = legacy.into_current();
+
+    assert_eq!(current.followed_users, Vec::<super::UserId>::new());
+    assert_eq!(current.total_point, 0);
+  }
+}
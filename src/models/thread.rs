@@ -0,0 +1,255 @@
+use std::collections::HashMap;
+
+use near_sdk::{
+  borsh::{self, BorshDeserialize, BorshSerialize},
+  serde::{Deserialize, Serialize},
+  json_types::U64,
+};
+use schemars::JsonSchema;
+
+use super::{space::SpaceId, user::UserId};
+
+/// `ThreadId` is a type alias for `String`, typically representing a unique identifier for a thread
+/// in the system.
+pub type ThreadId = String;
+
+/// Lifecycle state of a thread, derived from its `start_time`/`end_time` window, or `Resolved`
+/// once `resolved_early` is set.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, PartialEq, JsonSchema)]
+#[serde(crate = "near_sdk::serde")]
+pub enum ThreadState {
+  Upcoming,
+  Open,
+  /// Ended because `end_time` passed.
+  Closed,
+  /// Ended early because a choice crossed the thread's `supermajority_threshold`.
+  Resolved,
+}
+
+/// The `ThreadMetadata` struct represents metadata for a Thread in the system.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, JsonSchema)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ThreadMetadata {
+  /// Unique identifier for the Thread, of type `ThreadId`.
+  pub thread_id: ThreadId,
+
+  /// Title of the thread.
+  pub title: String,
+
+  /// Optional media attached to the thread.
+  pub media_link: Option<String>,
+
+  /// Creator's account ID.
+  pub creator_id: UserId,
+
+  /// Body content of the thread.
+  pub content: Option<String>,
+
+  /// Points the creator staked to open the thread.
+  pub init_point: u32,
+
+  /// Name of the space the thread belongs to.
+  pub space_name: String,
+
+  pub start_time: u64,
+
+  pub end_time: u64,
+
+  pub created_at: u64,
+
+  /// Number of voting choices on this thread.
+  pub choices_count: u8,
+
+  /// Choice index to its label.
+  pub choices_map: HashMap<u8, String>,
+
+  /// Voter to the vote they cast: `(choice_number, point, lockout_expiry_ms, confirmation_count)`.
+  ///
+  /// `lockout_expiry_ms` is the block timestamp after which the stake may be reclaimed via
+  /// `withdraw_locked_points`, and doubles (relative to `INITIAL_LOCKOUT_MS`) every time the
+  /// voter re-affirms the same choice, mirroring a tower-style lockout.
+  pub user_votes_map: HashMap<UserId, (u8, u32, u64, u8)>,
+
+  /// Choice index to its accumulated staked points.
+  pub choices_rating: HashMap<u8, u32>,
+
+  pub last_id: u32,
+
+  /// Set once `end_thread` has tallied and paid out the pot, so it cannot run twice.
+  pub settled: bool,
+
+  /// The winning `choice_number` once `end_thread` has settled the thread.
+  pub winning_choice: Option<u8>,
+
+  /// Percentage (51-90) of total staked points a single choice must reach to auto-resolve the
+  /// thread before `end_time`.
+  pub supermajority_threshold: u8,
+
+  /// Set once a choice has crossed `supermajority_threshold`, closing the thread early.
+  pub resolved_early: bool,
+}
+
+/// Default supermajority threshold when `create_thread` does not pick one: two-thirds.
+pub const DEFAULT_SUPERMAJORITY_THRESHOLD: u8 = 67;
+
+/// Base lockout duration for a vote's first confirmation; doubles per re-affirmation.
+pub const INITIAL_LOCKOUT_MS: u64 = 24 * 60 * 60 * 1_000;
+
+/// Cap on how many times a vote's lockout may double. `INITIAL_LOCKOUT_MS * 2^32` is still well
+/// under `u64::MAX`, so re-affirming far beyond this just keeps the lock at its max duration
+/// instead of wrapping `confirmation_count`'s doubling around to zero.
+pub const MAX_LOCKOUT_DOUBLINGS: u32 = 32;
+
+/// The pre-lockout, pre-settlement shape of `ThreadMetadata`, kept only so
+/// `ThreadMetadataV1::into_current` can migrate entries written before the
+/// `settled`/`winning_choice`/`supermajority_threshold`/`resolved_early` fields existed. Lives
+/// only in `ThreadScoreContract::legacy_thread_metadata_by_id` — see that field's doc comment.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, JsonSchema)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ThreadMetadataV1 {
+  pub thread_id: ThreadId,
+  pub title: String,
+  pub media_link: Option<String>,
+  pub creator_id: UserId,
+  pub content: Option<String>,
+  pub init_point: u32,
+  pub space_name: String,
+  pub start_time: u64,
+  pub end_time: u64,
+  pub created_at: u64,
+  pub choices_count: u8,
+  pub choices_map: HashMap<u8, String>,
+  pub user_votes_map: HashMap<UserId, (u8, u32)>,
+  pub choices_rating: HashMap<u8, u32>,
+  pub last_id: u32,
+}
+
+impl ThreadMetadataV1 {
+  /// Upgrades a pre-versioning entry to the current `ThreadMetadata` shape, filling sane
+  /// defaults for fields that didn't exist yet when it was written.
+  ///
+  /// There is no tag to branch on here: `ThreadMetadataV1` entries only ever live in
+  /// `ThreadScoreContract::legacy_thread_metadata_by_id`, a storage prefix that's never written
+  /// to after this migration shipped, while current entries live under the separate
+  /// `thread_metadata_by_id` prefix. Which shape a given `thread_id` is in is therefore
+  /// determined by which map the key is found in, not by guessing from its serialized bytes.
+  pub fn into_current(self) -> ThreadMetadata {
+    ThreadMetadata {
+      thread_id: self.thread_id,
+      title: self.title,
+      media_link: self.media_link,
+      creator_id: self.creator_id,
+      content: self.content,
+      init_point: self.init_point,
+      space_name: self.space_name,
+      start_time: self.start_time,
+      end_time: self.end_time,
+      created_at: self.created_at,
+      choices_count: self.choices_count,
+      choices_map: self.choices_map,
+      // votes cast before the lockout feature existed are treated as already unlocked
+      user_votes_map: self
+        .user_votes_map
+        .into_iter()
+        .map(|(voter, (choice, point))| (voter, (choice, point, 0_u64, 0_u8)))
+        .collect(),
+      choices_rating: self.choices_rating,
+      last_id: self.last_id,
+      settled: false,
+      winning_choice: None,
+      supermajority_threshold: DEFAULT_SUPERMAJORITY_THRESHOLD,
+      resolved_early: false,
+    }
+  }
+}
+
+pub trait ThreadFeatures {
+  fn create_thread(
+    &mut self,
+    title: String,
+    content: Option<String>,
+    media_link: Option<String>,
+    init_point: u32,
+    space_name: String,
+    start_time: U64,
+    end_time: U64,
+    options: Vec<String>,
+    supermajority_threshold: Option<u8>,
+  ) -> ThreadMetadata;
+
+  fn get_thread_metadata_by_thread_id(&self, thread_id: ThreadId) -> Option<ThreadMetadata>;
+
+  fn get_all_threads_per_user_own(
+    &self,
+    user_id: UserId,
+    start: Option<u32>,
+    limit: Option<u32>,
+  ) -> Vec<ThreadMetadata>;
+
+  fn get_thread_status(&self, thread_id: &ThreadId) -> ThreadState;
+
+  /// Casts a vote, optionally on behalf of `on_behalf_of` if the caller is that account's
+  /// currently-authorized delegate for the thread's space (see `set_authorized_voter`).
+  fn vote_thread(
+    &mut self,
+    thread_id: ThreadId,
+    choice_number: u8,
+    point: u32,
+    on_behalf_of: Option<UserId>,
+  ) -> Option<String>;
+
+  fn end_thread(&mut self, thread_id: ThreadId) -> Option<String>;
+
+  /// Returns a voter's stake to their `total_point` once its lockout has expired and the
+  /// thread is `Closed` or `Resolved` and settled by `end_thread`. Only stakes on the winning
+  /// choice are returned; losing stakes stay forfeited.
+  fn withdraw_locked_points(&mut self, thread_id: ThreadId) -> Option<String>;
+
+  /// Designates `delegate` as the caller's authorized voter within `space_id`, optionally
+  /// expiring at `expires_at`. Overrides any prior delegation for the same `(caller, space_id)`.
+  fn set_authorized_voter(&mut self, space_id: SpaceId, delegate: UserId, expires_at: Option<U64>) -> Option<String>;
+
+  /// Recency-weighted reputation derived from `UserMetadata`'s epoch credit history, or `0` if
+  /// the user does not exist.
+  fn get_trust_score(&self, user_id: UserId) -> u64;
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn thread_metadata_v1_upgrades_votes_as_already_unlocked() {
+    let mut user_votes_map = HashMap::new();
+    user_votes_map.insert("alice.near".parse().unwrap(), (0_u8, 42_u32));
+
+    let legacy = ThreadMetadataV1 {
+      thread_id: "old-thread".to_string(),
+      title: "Old thread".to_string(),
+      media_link: None,
+      creator_id: "bob.near".parse().unwrap(),
+      content: None,
+      init_point: 10,
+      space_name: "general".to_string(),
+      start_time: 0,
+      end_time: 1_000,
+      created_at: 0,
+      choices_count: 2,
+      choices_map: HashMap::new(),
+      user_votes_map,
+      choices_rating: HashMap::new(),
+      last_id: 0,
+    };
+
+    let current = legacy.into_current();
+
+    assert!(!current.settled);
+    assert_eq!(current.winning_choice, None);
+    assert_eq!(current.supermajority_threshold, DEFAULT_SUPERMAJORITY_THRESHOLD);
+    assert!(!current.resolved_early);
+    assert_eq!(
+      current.user_votes_map.get(&"alice.near".parse().unwrap()),
+      Some(&(0_u8, 42_u32, 0_u64, 0_u8))
+    );
+  }
+}
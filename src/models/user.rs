@@ -0,0 +1,198 @@
+use std::collections::VecDeque;
+
+use near_sdk::{
+  borsh::{self, BorshDeserialize, BorshSerialize},
+  serde::{Deserialize, Serialize},
+  AccountId,
+};
+use schemars::JsonSchema;
+
+use super::thread::ThreadId;
+
+/// A single epoch's credit tally: `(epoch, credits, prev_credits)`, mirroring a ring of
+/// recent-activity snapshots rather than one undifferentiated running total.
+pub type EpochCredits = (u64, u64, u64);
+
+/// Length of one epoch, in milliseconds, over which earned points are bucketed.
+pub const EPOCH_DURATION_MS: u64 = 24 * 60 * 60 * 1_000;
+
+/// Bound on `UserMetadata::epoch_credits_history` so reputation history cannot grow forever.
+pub const MAX_EPOCH_CREDITS_HISTORY: usize = 64;
+
+/// Per-epoch retention factor (in basis points) used to down-weight older epochs in
+/// `UserMetadata::trust_score`.
+pub const EPOCH_DECAY_BASIS_POINTS: u128 = 9_000;
+
+/// `UserId` is a type alias for `AccountId`, identifying a user by their NEAR account.
+pub type UserId = AccountId;
+
+/// Role assigned to a user account, gating privileged actions such as thread creation.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, PartialEq, JsonSchema)]
+#[serde(crate = "near_sdk::serde")]
+pub enum UserRoles {
+  Unverified,
+  Verified,
+  Admin,
+}
+
+/// Profile-level metadata for a user, separate from their trust-point balance.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, JsonSchema)]
+#[serde(crate = "near_sdk::serde")]
+pub struct UserProfileMetadata {
+  pub role: UserRoles,
+  pub username: Option<String>,
+}
+
+/// The `UserMetadata` struct represents metadata for a user account in the system.
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, JsonSchema)]
+#[serde(crate = "near_sdk::serde")]
+pub struct UserMetadata {
+  /// Unique identifier for the user, of type `UserId`.
+  pub user_id: UserId,
+
+  /// Profile-level metadata, including the account's `UserRoles`.
+  pub metadata: UserProfileMetadata,
+
+  /// Spendable trust point balance, staked into threads and refunded/rewarded on settlement.
+  pub total_point: u32,
+
+  /// Number of threads created by this user.
+  pub threads_owned: u32,
+
+  /// Ids of threads created by this user.
+  pub threads_list: Vec<ThreadId>,
+
+  pub created_at: u64,
+
+  /// Ring of recent per-epoch credit tallies backing `trust_score`, capped at
+  /// `MAX_EPOCH_CREDITS_HISTORY` entries.
+  pub epoch_credits_history: VecDeque<EpochCredits>,
+}
+
+impl UserMetadata {
+  /// Records `credits_gained` against `epoch`, merging into the most recent entry if it's the
+  /// same epoch, otherwise appending a new one and evicting the oldest past the history cap.
+  pub fn record_epoch_credits(&mut self, epoch: u64, credits_gained: u64) {
+    if credits_gained == 0 {
+      return;
+    }
+
+    match self.epoch_credits_history.back_mut() {
+      // `prev_credits` is the snapshot from *before this epoch started* — it must stay put
+      // across same-epoch merges, or a second gain in the same epoch overwrites it with the
+      // value from just before that gain and silently drops everything earned earlier.
+      Some((last_epoch, credits, _)) if *last_epoch == epoch => {
+        *credits += credits_gained;
+      },
+      _ => {
+        let prev_credits = self.epoch_credits_history.back().map_or(0, |(_, credits, _)| *credits);
+
+        self.epoch_credits_history.push_back((epoch, prev_credits + credits_gained, prev_credits));
+
+        if self.epoch_credits_history.len() > MAX_EPOCH_CREDITS_HISTORY {
+          self.epoch_credits_history.pop_front();
+        }
+      },
+    }
+  }
+
+  /// Recency-weighted reputation: the credits earned in each epoch, discounted by
+  /// `EPOCH_DECAY_BASIS_POINTS` for every epoch between it and the most recent entry in the
+  /// history (not the current time), summed together. This
+  /// turns the flat `total_point` balance into a time-aware trust score.
+  pub fn trust_score(&self) -> u64 {
+    let latest_epoch = match self.epoch_credits_history.back() {
+      Some((epoch, _, _)) => *epoch,
+      None => return 0,
+    };
+
+    self
+      .epoch_credits_history
+      .iter()
+      .map(|(epoch, credits, prev_credits)| {
+        let credits_earned_this_epoch = credits.saturating_sub(*prev_credits) as u128;
+        let epochs_ago = latest_epoch.saturating_sub(*epoch) as u32;
+
+        // `weight_basis_points` only shrinks, so once it rounds down to 0 every further
+        // iteration would be a no-op — break out instead of grinding through the rest of
+        // `epochs_ago`, which can be in the thousands for an old, long-inactive entry.
+        let mut weight_basis_points = 10_000_u128;
+        for _ in 0..epochs_ago {
+          if weight_basis_points == 0 {
+            break;
+          }
+          weight_basis_points = weight_basis_points * EPOCH_DECAY_BASIS_POINTS / 10_000;
+        }
+
+        (credits_earned_this_epoch * weight_basis_points / 10_000) as u64
+      })
+      .sum()
+  }
+}
+
+pub trait UserFeatures {
+  fn create_user_account(&mut self, username: Option<String>) -> UserMetadata;
+
+  fn get_user_metadata_by_user_id(&self, user_id: UserId) -> Option<UserMetadata>;
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn user(epoch_credits_history: VecDeque<EpochCredits>) -> UserMetadata {
+    UserMetadata {
+      user_id: "alice.near".parse().unwrap(),
+      metadata: UserProfileMetadata { role: UserRoles::Verified, username: None },
+      total_point: 0,
+      threads_owned: 0,
+      threads_list: Vec::new(),
+      created_at: 0,
+      epoch_credits_history,
+    }
+  }
+
+  #[test]
+  fn record_epoch_credits_merges_within_the_same_epoch_without_losing_earlier_gains() {
+    let mut metadata = user(VecDeque::new());
+    metadata.record_epoch_credits(5, 10);
+    metadata.record_epoch_credits(5, 7);
+
+    assert_eq!(metadata.epoch_credits_history.len(), 1);
+    assert_eq!(metadata.epoch_credits_history.back(), Some(&(5, 17, 0)));
+    assert_eq!(metadata.trust_score(), 17);
+  }
+
+  #[test]
+  fn record_epoch_credits_starts_a_new_entry_for_a_new_epoch() {
+    let mut metadata = user(VecDeque::new());
+    metadata.record_epoch_credits(5, 10);
+    metadata.record_epoch_credits(6, 4);
+
+    assert_eq!(metadata.epoch_credits_history.len(), 2);
+    assert_eq!(metadata.epoch_credits_history.back(), Some(&(6, 14, 10)));
+  }
+
+  #[test]
+  fn record_epoch_credits_evicts_the_oldest_entry_past_the_history_cap() {
+    let mut metadata = user(VecDeque::new());
+    for epoch in 0..(MAX_EPOCH_CREDITS_HISTORY as u64 + 1) {
+      metadata.record_epoch_credits(epoch, 1);
+    }
+
+    assert_eq!(metadata.epoch_credits_history.len(), MAX_EPOCH_CREDITS_HISTORY);
+    assert_eq!(metadata.epoch_credits_history.front().unwrap().0, 1);
+  }
+
+  #[test]
+  fn trust_score_decays_older_epochs_to_zero_instead_of_looping_forever_on_a_stale_gap() {
+    let mut history = VecDeque::new();
+    history.push_back((0, 100, 0));
+    history.push_back((10_000, 200, 100));
+    let metadata = user(history);
+
+    // the epoch-0 entry is 10,000 epochs stale: its decay weight rounds down to 0 long before
+    // that many loop iterations, so it should contribute nothing to the score.
+    assert_eq!(metadata.trust_score(), 100);
+  }
+}